@@ -0,0 +1,423 @@
+use std::sync::{Arc, Mutex};
+
+use eframe::egui;
+
+use crate::renderer3d::Renderer3d;
+
+/// `Renderer3d` implementation that draws the cube with glow (OpenGL).
+pub struct GlowRenderer {
+    rotating_triangle: Arc<Mutex<RotatingTriangle>>,
+}
+
+impl GlowRenderer {
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        let gl = cc
+            .gl
+            .as_ref()
+            .expect("You need to run eframe with glow backend");
+        Self {
+            rotating_triangle: Arc::new(Mutex::new(RotatingTriangle::new(gl))),
+        }
+    }
+}
+
+impl Renderer3d for GlowRenderer {
+    fn paint(&mut self, ui: &mut egui::Ui, rect: egui::Rect, angle: f32) {
+        let aspect_ratio = rect.width() / rect.height();
+        let rotating_triangle = self.rotating_triangle.clone();
+
+        let callback = egui::PaintCallback {
+            rect,
+            callback: Arc::new(egui_glow::CallbackFn::new(move |_info, painter| {
+                rotating_triangle
+                    .lock()
+                    .expect("Cannot lock mutex to paint triangle.")
+                    .paint(painter.gl(), angle, aspect_ratio);
+            })),
+        };
+        ui.painter().add(callback);
+    }
+
+    fn capture(
+        &self,
+        gl: Option<&glow::Context>,
+        angle: f32,
+        width: u32,
+        height: u32,
+    ) -> Option<Vec<u8>> {
+        let gl = gl?;
+        Some(
+            self.rotating_triangle
+                .lock()
+                .expect("Cannot lock mutex to capture triangle.")
+                .capture(gl, angle, width, height),
+        )
+    }
+
+    fn destroy(&mut self, gl: Option<&glow::Context>) {
+        if let Some(gl) = gl {
+            self.rotating_triangle
+                .lock()
+                .expect("Cannot lock mutex to destroy triangle.")
+                .destroy(gl);
+        }
+    }
+}
+
+struct RotatingTriangle {
+    program: glow::Program,
+    vertex_array_object: glow::NativeVertexArray,
+    vertex_buffer_object: glow::NativeBuffer,
+    index_buffer_object: glow::NativeBuffer,
+    index_count: i32,
+    framebuffer_object: glow::NativeFramebuffer,
+    texture: glow::NativeTexture,
+    depth_renderbuffer: glow::NativeRenderbuffer,
+    counter: f32,
+}
+
+impl RotatingTriangle {
+    fn new(gl: &glow::Context) -> Self {
+        use glow::HasContext as _;
+
+        #[rustfmt::skip]
+        let vertices = [
+            // position                   color
+            -0.5f32, -0.5f32, -0.5f32,    1.0f32, 0.0f32, 0.0f32,
+             0.5f32, -0.5f32, -0.5f32,    0.0f32, 1.0f32, 0.0f32,
+             0.5f32,  0.5f32, -0.5f32,    0.0f32, 0.0f32, 1.0f32,
+            -0.5f32,  0.5f32, -0.5f32,    1.0f32, 1.0f32, 0.0f32,
+            -0.5f32, -0.5f32,  0.5f32,    1.0f32, 0.0f32, 1.0f32,
+             0.5f32, -0.5f32,  0.5f32,    0.0f32, 1.0f32, 1.0f32,
+             0.5f32,  0.5f32,  0.5f32,    1.0f32, 1.0f32, 1.0f32,
+            -0.5f32,  0.5f32,  0.5f32,    0.0f32, 0.0f32, 0.0f32,
+        ];
+        #[rustfmt::skip]
+        let indices = [
+            0u32, 1u32, 2u32, 2u32, 3u32, 0u32, // back
+            4u32, 5u32, 6u32, 6u32, 7u32, 4u32, // front
+            0u32, 3u32, 7u32, 7u32, 4u32, 0u32, // left
+            1u32, 5u32, 6u32, 6u32, 2u32, 1u32, // right
+            0u32, 4u32, 5u32, 5u32, 1u32, 0u32, // bottom
+            3u32, 2u32, 6u32, 6u32, 7u32, 3u32, // top
+        ];
+
+        unsafe {
+            let program = create_program(gl);
+
+            let vertex_buffer_object = gl.create_buffer().expect("Cannot create vertex buffer.");
+
+            let vertex_array_object = gl
+                .create_vertex_array()
+                .expect("Cannot create vertex array.");
+
+            let index_buffer_object = gl.create_buffer().expect("Cannot create index buffer.");
+
+            let vertices_u8: &[u8] = core::slice::from_raw_parts(
+                vertices.as_ptr() as *const u8,
+                vertices.len() * core::mem::size_of::<f32>(),
+            );
+            let indices_u8: &[u8] = core::slice::from_raw_parts(
+                indices.as_ptr() as *const u8,
+                indices.len() * core::mem::size_of::<u32>(),
+            );
+
+            gl.bind_vertex_array(Some(vertex_array_object));
+
+            gl.bind_buffer(glow::ARRAY_BUFFER, Some(vertex_buffer_object));
+            gl.buffer_data_u8_slice(glow::ARRAY_BUFFER, vertices_u8, glow::STATIC_DRAW);
+            gl.enable_vertex_attrib_array(0);
+            gl.vertex_attrib_pointer_f32(0, 3, glow::FLOAT, false, 24, 0);
+            gl.enable_vertex_attrib_array(1);
+            gl.vertex_attrib_pointer_f32(1, 3, glow::FLOAT, false, 24, 12);
+
+            gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, Some(index_buffer_object));
+            gl.buffer_data_u8_slice(glow::ELEMENT_ARRAY_BUFFER, indices_u8, glow::STATIC_DRAW);
+
+            let framebuffer_object = gl.create_framebuffer().expect("Cannot create framebuffer.");
+            let texture = gl.create_texture().expect("Cannot create texture.");
+            let depth_renderbuffer = gl
+                .create_renderbuffer()
+                .expect("Cannot create depth renderbuffer.");
+
+            Self {
+                program,
+                vertex_array_object,
+                vertex_buffer_object,
+                index_buffer_object,
+                index_count: indices.len() as i32,
+                framebuffer_object,
+                texture,
+                depth_renderbuffer,
+                counter: 0.0f32,
+            }
+        }
+    }
+
+    fn destroy(&self, gl: &glow::Context) {
+        use glow::HasContext as _;
+        unsafe {
+            gl.delete_program(self.program);
+            gl.delete_vertex_array(self.vertex_array_object);
+            gl.delete_buffer(self.vertex_buffer_object);
+            gl.delete_buffer(self.index_buffer_object);
+            gl.delete_framebuffer(self.framebuffer_object);
+            gl.delete_texture(self.texture);
+            gl.delete_renderbuffer(self.depth_renderbuffer);
+        }
+    }
+
+    /// Binds the program and VAO, uploads the animated uniforms, and draws the cube into
+    /// whichever framebuffer is currently bound. Shared by `paint` (default framebuffer) and
+    /// `capture` (offscreen framebuffer).
+    unsafe fn draw_scene(&self, gl: &glow::Context, angle: f32, aspect_ratio: f32) {
+        use glow::HasContext as _;
+
+        gl.bind_vertex_array(Some(self.vertex_array_object));
+        gl.use_program(Some(self.program));
+
+        let (sin_c, cos_c) = (self.counter * std::f32::consts::TAU).sin_cos();
+        #[rustfmt::skip]
+        let color_flow: [f32; 16] = [
+            cos_c, sin_c, 0.0, 0.0,
+            -sin_c, cos_c, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+        ];
+        let color_flow_location = gl.get_uniform_location(self.program, "u_color_flow");
+        gl.uniform_matrix_4_f32_slice(color_flow_location.as_ref(), false, &color_flow);
+
+        // Rotate about the Y axis so dragging turns the cube like a turntable, revealing its
+        // side and back faces instead of just spinning the front face's silhouette in place.
+        let (sin_a, cos_a) = angle.sin_cos();
+        #[rustfmt::skip]
+        let transform: [f32; 16] = [
+            cos_a, 0.0, -sin_a, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            sin_a, 0.0, cos_a, 0.0,
+            0.0, 0.0, -3.0, 1.0,
+        ];
+        let transform_location = gl.get_uniform_location(self.program, "u_transform");
+        gl.uniform_matrix_4_f32_slice(transform_location.as_ref(), false, &transform);
+
+        let fovy: f32 = 45.0f32.to_radians();
+        let (near, far) = (0.1f32, 10.0f32);
+        let f = 1.0 / (fovy / 2.0).tan();
+        #[rustfmt::skip]
+        let projection: [f32; 16] = [
+            f / aspect_ratio, 0.0, 0.0, 0.0,
+            0.0, f, 0.0, 0.0,
+            0.0, 0.0, (far + near) / (near - far), -1.0,
+            0.0, 0.0, (2.0 * far * near) / (near - far), 0.0,
+        ];
+        let projection_location = gl.get_uniform_location(self.program, "u_projection");
+        gl.uniform_matrix_4_f32_slice(projection_location.as_ref(), false, &projection);
+
+        gl.draw_elements(glow::TRIANGLES, self.index_count, glow::UNSIGNED_INT, 0);
+    }
+
+    fn paint(&mut self, gl: &glow::Context, angle: f32, aspect_ratio: f32) {
+        use glow::HasContext as _;
+
+        unsafe {
+            let depth_test_was_enabled = gl.is_enabled(glow::DEPTH_TEST);
+            gl.enable(glow::DEPTH_TEST);
+            gl.clear(glow::DEPTH_BUFFER_BIT);
+
+            self.draw_scene(gl, angle, aspect_ratio);
+
+            if !depth_test_was_enabled {
+                gl.disable(glow::DEPTH_TEST);
+            }
+
+            if self.counter > 1.0f32 {
+                self.counter = 0.0f32;
+            }
+
+            self.counter += 0.05f32;
+        }
+    }
+
+    /// Renders one frame of the scene, at the given `angle`, into an offscreen framebuffer
+    /// and reads it back as a flipped (top-down) RGBA byte buffer, ready to hand to an image
+    /// encoder.
+    fn capture(&self, gl: &glow::Context, angle: f32, width: u32, height: u32) -> Vec<u8> {
+        use glow::HasContext as _;
+
+        let (width, height) = (width as i32, height as i32);
+
+        unsafe {
+            let mut previous_viewport = [0i32; 4];
+            gl.get_parameter_i32_slice(glow::VIEWPORT, &mut previous_viewport);
+
+            gl.bind_framebuffer(glow::FRAMEBUFFER, Some(self.framebuffer_object));
+
+            gl.bind_texture(glow::TEXTURE_2D, Some(self.texture));
+            gl.tex_image_2d(
+                glow::TEXTURE_2D,
+                0,
+                glow::RGBA as i32,
+                width,
+                height,
+                0,
+                glow::RGBA,
+                glow::UNSIGNED_BYTE,
+                None,
+            );
+            gl.framebuffer_texture_2d(
+                glow::FRAMEBUFFER,
+                glow::COLOR_ATTACHMENT0,
+                glow::TEXTURE_2D,
+                Some(self.texture),
+                0,
+            );
+
+            gl.bind_renderbuffer(glow::RENDERBUFFER, Some(self.depth_renderbuffer));
+            gl.renderbuffer_storage(glow::RENDERBUFFER, glow::DEPTH_COMPONENT24, width, height);
+            gl.framebuffer_renderbuffer(
+                glow::FRAMEBUFFER,
+                glow::DEPTH_ATTACHMENT,
+                glow::RENDERBUFFER,
+                Some(self.depth_renderbuffer),
+            );
+
+            let depth_test_was_enabled = gl.is_enabled(glow::DEPTH_TEST);
+            gl.viewport(0, 0, width, height);
+            gl.enable(glow::DEPTH_TEST);
+            gl.clear_color(0.0, 0.0, 0.0, 1.0);
+            gl.clear(glow::COLOR_BUFFER_BIT | glow::DEPTH_BUFFER_BIT);
+
+            self.draw_scene(gl, angle, width as f32 / height as f32);
+
+            let mut pixels = vec![0u8; (width * height * 4) as usize];
+            gl.read_pixels(
+                0,
+                0,
+                width,
+                height,
+                glow::RGBA,
+                glow::UNSIGNED_BYTE,
+                glow::PixelPackData::Slice(&mut pixels),
+            );
+
+            if !depth_test_was_enabled {
+                gl.disable(glow::DEPTH_TEST);
+            }
+
+            gl.bind_framebuffer(glow::FRAMEBUFFER, None);
+            gl.viewport(
+                previous_viewport[0],
+                previous_viewport[1],
+                previous_viewport[2],
+                previous_viewport[3],
+            );
+
+            flip_rows_vertically(&mut pixels, width as usize, height as usize);
+            pixels
+        }
+    }
+}
+
+/// OpenGL reads pixels with the origin at the bottom-left, but image formats like PNG expect
+/// the first row to be the top of the image, so the rows need flipping before encoding.
+fn flip_rows_vertically(pixels: &mut [u8], width: usize, height: usize) {
+    let stride = width * 4;
+    for row in 0..height / 2 {
+        let top = row * stride;
+        let bottom = (height - 1 - row) * stride;
+        for offset in 0..stride {
+            pixels.swap(top + offset, bottom + offset);
+        }
+    }
+}
+
+unsafe fn create_program(gl: &glow::Context) -> glow::NativeProgram {
+    use glow::HasContext as _;
+
+    let program = gl.create_program().expect("Cannot create program.");
+
+    let shader_version = if cfg!(target_arch = "wasm32") {
+        "#version 300 es"
+    } else {
+        "#version 330"
+    };
+
+    let vertex_shader = create_shader(
+        gl,
+        glow::VERTEX_SHADER,
+        VERTEX_SHADER_SOURCE,
+        shader_version,
+    );
+    let fragment_shader = create_shader(
+        gl,
+        glow::FRAGMENT_SHADER,
+        FRAGMENT_SHADER_SOURCE,
+        shader_version,
+    );
+
+    gl.attach_shader(program, vertex_shader);
+    gl.attach_shader(program, fragment_shader);
+
+    gl.link_program(program);
+    assert!(
+        gl.get_program_link_status(program),
+        "{}",
+        gl.get_program_info_log(program)
+    );
+
+    gl.detach_shader(program, vertex_shader);
+    gl.detach_shader(program, fragment_shader);
+
+    gl.delete_shader(vertex_shader);
+    gl.delete_shader(fragment_shader);
+
+    program
+}
+
+unsafe fn create_shader(
+    gl: &glow::Context,
+    shader_type: u32,
+    shader_source: &str,
+    shader_version: &str,
+) -> glow::NativeShader {
+    use glow::HasContext as _;
+
+    let shader = gl
+        .create_shader(shader_type)
+        .expect("Cannot create shader.");
+    gl.shader_source(shader, &format!("{shader_version}\n{shader_source}"));
+    gl.compile_shader(shader);
+    assert!(
+        gl.get_shader_compile_status(shader),
+        "Failed to compile {shader_type}: {}",
+        gl.get_shader_info_log(shader)
+    );
+
+    shader
+}
+
+const VERTEX_SHADER_SOURCE: &str = r#"
+    layout(location = 0) in vec3 position;
+    layout(location = 1) in vec3 color;
+
+    uniform mat4 u_transform;
+    uniform mat4 u_projection;
+    uniform mat4 u_color_flow;
+
+    out vec4 vertexColor;
+
+    void main() {
+        gl_Position = u_projection * u_transform * vec4(position, 1.0);
+        vertexColor = u_color_flow * vec4(color, 1.0);
+    }
+"#;
+
+const FRAGMENT_SHADER_SOURCE: &str = r#"
+    in vec4 vertexColor;
+
+    layout(location = 0) out vec4 color;
+
+    void main() {
+        color = vertexColor;
+    }
+"#;
@@ -1,245 +1,137 @@
-use std::sync::{Arc, Mutex};
+mod glow_renderer;
+mod renderer3d;
+mod wgpu_renderer;
 
 use eframe::egui;
 
+use glow_renderer::GlowRenderer;
+use renderer3d::Renderer3d;
+use wgpu_renderer::WgpuRenderer;
+
+fn app_creator() -> eframe::AppCreator {
+    Box::new(|cc| Box::new(MyApp::new(cc)))
+}
+
+/// Picks the rendering backend from the `CUBE_RENDERER` environment variable (`glow` or
+/// `wgpu`, case-insensitive), defaulting to `glow`. This is what actually makes
+/// `MyApp::new`'s `Renderer3d` selection reachable at runtime, rather than requiring a
+/// source edit and recompile to try the other backend.
+#[cfg(not(target_arch = "wasm32"))]
+fn renderer_from_env() -> eframe::Renderer {
+    match std::env::var("CUBE_RENDERER") {
+        Ok(value) if value.eq_ignore_ascii_case("wgpu") => eframe::Renderer::Wgpu,
+        _ => eframe::Renderer::Glow,
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
 fn main() -> eframe::Result<()> {
     env_logger::init();
 
-    let app_name: &str = "Custom 3D painting in eframe using glow";
+    let app_name: &str = "Custom 3D painting in eframe";
     let native_options: eframe::NativeOptions = eframe::NativeOptions {
         initial_window_size: Some(egui::vec2(350.0, 380.0)),
         multisampling: 4,
-        renderer: eframe::Renderer::Glow,
+        depth_buffer: 24,
+        stencil_buffer: 8,
+        renderer: renderer_from_env(),
         ..Default::default()
     };
-    let app_creator: eframe::AppCreator = Box::new(|cc| Box::new(MyApp::new(cc)));
-    eframe::run_native(app_name, native_options, app_creator)
+    eframe::run_native(app_name, native_options, app_creator())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn main() {}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen::prelude::wasm_bindgen(start)]
+pub fn start() -> Result<(), wasm_bindgen::JsValue> {
+    console_error_panic_hook::set_once();
+    eframe::WebLogger::init(log::LevelFilter::Debug).ok();
+
+    let web_options = eframe::WebOptions::default();
+
+    wasm_bindgen_futures::spawn_local(async {
+        eframe::WebRunner::new()
+            .start("the_canvas_id", web_options, app_creator())
+            .await
+            .expect("Failed to start eframe web runner.");
+    });
+
+    Ok(())
 }
+
+const SCREENSHOT_SIZE: u32 = 300;
+
 struct MyApp {
-    rotatin_triangle: Arc<Mutex<RotatingTriangle>>,
+    renderer: Box<dyn Renderer3d>,
+    angle: f32,
 }
 
 impl MyApp {
     fn new(cc: &eframe::CreationContext<'_>) -> Self {
-        let gl = cc
-            .gl
-            .as_ref()
-            .expect("You need to run eframe with glow backend");
+        let renderer: Box<dyn Renderer3d> = if cc.wgpu_render_state.is_some() {
+            Box::new(WgpuRenderer::new(cc))
+        } else {
+            Box::new(GlowRenderer::new(cc))
+        };
         Self {
-            rotatin_triangle: Arc::new(Mutex::new(RotatingTriangle::new(gl))),
+            renderer,
+            angle: 0.0f32,
         }
     }
 
     fn custom_painting(&mut self, ui: &mut egui::Ui) {
-        let (rect, _response) =
-            ui.allocate_exact_size(egui::Vec2::splat(300.0), egui::Sense::hover());
-
-        let rotating_triangle = self.rotatin_triangle.clone();
-
-        let callback = egui::PaintCallback {
-            rect,
-            callback: Arc::new(egui_glow::CallbackFn::new(move |_info, painter| {
-                rotating_triangle
-                    .lock()
-                    .expect("Cannot lock mutex to paint triangle.")
-                    .paint(painter.gl());
-            })),
-        };
-        ui.painter().add(callback);
+        let (rect, response) =
+            ui.allocate_exact_size(egui::Vec2::splat(300.0), egui::Sense::drag());
+
+        self.angle += response.drag_delta().x * 0.01;
+
+        self.renderer.paint(ui, rect, self.angle);
     }
 }
 
 impl eframe::App for MyApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.horizontal(|ui| {
                 ui.spacing_mut().item_spacing.x = 0.0;
-                ui.label("The triangle is being painted using ");
+                ui.label("The cube is being painted using ");
                 ui.hyperlink_to("glow", "https://github.com/grovesNL/glow");
-                ui.label(" (OpenGL).")
+                ui.label(" or ");
+                ui.hyperlink_to("wgpu", "https://github.com/gfx-rs/wgpu");
+                ui.label(".")
             });
 
             egui::Frame::canvas(ui.style()).show(ui, |ui| {
                 self.custom_painting(ui);
             });
-        });
-    }
-
-    fn on_exit(&mut self, gl: Option<&glow::Context>) {
-        if let Some(gl) = gl {
-            self.rotatin_triangle
-                .lock()
-                .expect("Cannot lock mutex to destroy triangle.")
-                .destroy(gl);
-        }
-    }
-}
-
-struct RotatingTriangle {
-    program: glow::Program,
-    vertex_array_object: glow::NativeVertexArray,
-    vertex_buffer_object: glow::NativeBuffer,
-    index_buffer_object: glow::NativeBuffer,
-    counter: f32,
-}
-
-impl RotatingTriangle {
-    fn new(gl: &glow::Context) -> Self {
-        use glow::HasContext as _;
-
-        unsafe {
-            let program = create_program(&gl);
-
-            let vertex_buffer_object = gl.create_buffer().expect("Cannot create vertex buffer.");
-
-            let vertex_array_object = gl
-                .create_vertex_array()
-                .expect("Cannot create vertex array.");
-
-            let index_buffer_object = gl.create_buffer().expect("Cannot create index buffer.");
 
-            Self {
-                program,
-                vertex_array_object,
-                vertex_buffer_object,
-                index_buffer_object,
-                counter: 0.0f32,
+            if ui.button("Save screenshot").clicked() {
+                match self.renderer.capture(
+                    frame.gl().map(|gl| gl.as_ref()),
+                    self.angle,
+                    SCREENSHOT_SIZE,
+                    SCREENSHOT_SIZE,
+                ) {
+                    Some(pixels) => {
+                        if let Err(err) = image::save_buffer(
+                            "screenshot.png",
+                            &pixels,
+                            SCREENSHOT_SIZE,
+                            SCREENSHOT_SIZE,
+                            image::ColorType::Rgba8,
+                        ) {
+                            log::error!("Failed to save screenshot: {err}");
+                        }
+                    }
+                    None => log::warn!("This renderer backend does not support screenshots."),
+                }
             }
-        }
-    }
-
-    fn destroy(&self, gl: &glow::Context) {
-        use glow::HasContext as _;
-        unsafe {
-            gl.delete_program(self.program);
-            gl.delete_vertex_array(self.vertex_array_object);
-            gl.delete_buffer(self.vertex_buffer_object);
-        }
+        });
     }
 
-    fn paint(&mut self, gl: &glow::Context) {
-        use glow::HasContext as _;
-
-        let vertices = [
-            -0.5f32, -0.5f32, 0.5f32, -0.5f32, 0.5f32, 0.5f32, -0.5f32, 0.5f32,
-        ];
-        let indices = [0u32, 1u32, 2u32, 2u32, 3u32, 0u32];
-
-        unsafe {
-            let vertices_u8: &[u8] = core::slice::from_raw_parts(
-                vertices.as_ptr() as *const u8,
-                vertices.len() * core::mem::size_of::<f32>(),
-            );
-            let indices_u8: &[u8] = core::slice::from_raw_parts(
-                indices.as_ptr() as *const u8,
-                indices.len() * core::mem::size_of::<u32>(),
-            );
-
-            gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.vertex_buffer_object));
-            gl.buffer_data_u8_slice(glow::ARRAY_BUFFER, vertices_u8, glow::STATIC_DRAW);
-
-            gl.bind_vertex_array(Some(self.vertex_array_object));
-            gl.enable_vertex_attrib_array(0);
-            gl.vertex_attrib_pointer_f32(0, 2, glow::FLOAT, false, 8, 0);
-
-            gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, Some(self.index_buffer_object));
-            gl.buffer_data_u8_slice(glow::ELEMENT_ARRAY_BUFFER, indices_u8, glow::STATIC_DRAW);
-
-            gl.use_program(Some(self.program));
-
-            let location = gl.get_uniform_location(self.program, "u_color");
-            gl.uniform_4_f32(location.as_ref(), self.counter, 0.2, 0.2, 1.0);
-
-            gl.draw_elements(glow::TRIANGLES, indices.len() as i32, glow::UNSIGNED_INT, 0);
-            
-            if self.counter > 1.0f32 {
-                self.counter = 0.0f32;
-            }
-
-            self.counter += 0.05f32;
-        }
+    fn on_exit(&mut self, gl: Option<&glow::Context>) {
+        self.renderer.destroy(gl);
     }
 }
-
-unsafe fn create_program(gl: &glow::Context) -> glow::NativeProgram {
-    use glow::HasContext as _;
-
-    let program = gl.create_program().expect("Cannot create program.");
-
-    let shader_version = if cfg!(target_arch = "wasm32") {
-        "#version 300 es"
-    } else {
-        "#version 330"
-    };
-
-    let vertex_shader = create_shader(
-        gl,
-        glow::VERTEX_SHADER,
-        VERTEX_SHADER_SOURCE,
-        shader_version,
-    );
-    let fragment_shader = create_shader(
-        gl,
-        glow::FRAGMENT_SHADER,
-        FRAGMENT_SHADER_SOURCE,
-        shader_version,
-    );
-
-    gl.attach_shader(program, vertex_shader);
-    gl.attach_shader(program, fragment_shader);
-
-    gl.link_program(program);
-    assert!(
-        gl.get_program_link_status(program),
-        "{}",
-        gl.get_program_info_log(program)
-    );
-
-    gl.detach_shader(program, vertex_shader);
-    gl.detach_shader(program, fragment_shader);
-
-    gl.delete_shader(vertex_shader);
-    gl.delete_shader(fragment_shader);
-
-    program
-}
-
-unsafe fn create_shader(
-    gl: &glow::Context,
-    shader_type: u32,
-    shader_source: &str,
-    shader_version: &str,
-) -> glow::NativeShader {
-    use glow::HasContext as _;
-
-    let shader = gl
-        .create_shader(shader_type)
-        .expect("Cannot create shader.");
-    gl.shader_source(shader, &format!("{shader_version}\n{shader_source}"));
-    gl.compile_shader(shader);
-    assert!(
-        gl.get_shader_compile_status(shader),
-        "Failed to compile {shader_type}: {}",
-        gl.get_shader_info_log(shader)
-    );
-
-    shader
-}
-
-const VERTEX_SHADER_SOURCE: &str = r#"
-    layout(location = 0) in vec4 position;
-
-    void main() {
-        gl_Position = position;
-    }
-"#;
-
-const FRAGMENT_SHADER_SOURCE: &str = r#"
-    layout(location = 0) out vec4 color;
-
-    uniform vec4 u_color;
-
-    void main() {
-        color = u_color;
-    }
-"#;
@@ -0,0 +1,32 @@
+use eframe::egui;
+
+/// A 3D scene that can be painted by whichever graphics backend eframe handed us
+/// (glow or wgpu), so `MyApp` doesn't need to know which one is actually in use.
+///
+/// Each implementation gets its own `new(cc: &eframe::CreationContext<'_>) -> Self`
+/// constructor rather than one on this trait, since glow pulls its context from `cc.gl`
+/// and wgpu from `cc.wgpu_render_state` and a shared `Self`-returning constructor isn't
+/// object-safe anyway; `MyApp::new` picks the right one and boxes the result.
+pub trait Renderer3d {
+    /// Schedules one frame of the scene to be painted into `rect`, driven by the current
+    /// drag `angle`.
+    fn paint(&mut self, ui: &mut egui::Ui, rect: egui::Rect, angle: f32);
+
+    /// Captures one offscreen frame, drawn at the given `angle`, as a flipped (top-down) RGBA
+    /// buffer, if the backend supports it. Backends that don't implement offscreen capture
+    /// return `None`.
+    fn capture(
+        &self,
+        gl: Option<&glow::Context>,
+        angle: f32,
+        width: u32,
+        height: u32,
+    ) -> Option<Vec<u8>> {
+        let _ = (gl, angle, width, height);
+        None
+    }
+
+    /// Releases backend-owned GPU resources on exit. `gl` is only relevant to the glow
+    /// backend; the wgpu backend's resources are dropped along with its `egui_wgpu::Renderer`.
+    fn destroy(&mut self, gl: Option<&glow::Context>);
+}
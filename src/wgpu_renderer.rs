@@ -0,0 +1,296 @@
+use eframe::{egui, egui_wgpu};
+use wgpu::util::DeviceExt as _;
+
+use crate::renderer3d::Renderer3d;
+
+/// `Renderer3d` implementation that draws the cube with wgpu, for backends where OpenGL
+/// isn't available (e.g. Metal/DX12). Mirrors `GlowRenderer`'s scene: a color-per-vertex
+/// cube driven by a model-view rotation, a perspective projection, and an animated
+/// R/G color-flow matrix, all uploaded as a single uniform buffer.
+///
+/// `paint` draws into egui's own `wgpu::RenderPass`, whose depth-stencil attachment is
+/// `egui_wgpu::depth_format_from_bits(native_options.depth_buffer, native_options.stencil_buffer)`
+/// — `Depth24PlusStencil8` for the `depth_buffer: 24, stencil_buffer: 8` set in `main.rs`.
+/// The pipeline's own `depth_stencil` state has to match that format exactly, or wgpu's
+/// render-pass compatibility check panics the first time the cube is drawn.
+pub struct WgpuRenderer {
+    counter: f32,
+}
+
+impl WgpuRenderer {
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        let wgpu_render_state = cc
+            .wgpu_render_state
+            .as_ref()
+            .expect("You need to run eframe with the wgpu backend");
+
+        let device = &wgpu_render_state.device;
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("cube shader"),
+            source: wgpu::ShaderSource::Wgsl(SHADER_SOURCE.into()),
+        });
+
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("cube vertex buffer"),
+            contents: bytemuck::cast_slice(&VERTICES),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("cube index buffer"),
+            contents: bytemuck::cast_slice(&INDICES),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("cube uniform buffer"),
+            size: std::mem::size_of::<Uniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("cube bind group layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("cube bind group"),
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("cube pipeline layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("cube pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[Vertex::layout()],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu_render_state.target_format.into())],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth24PlusStencil8,
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::Less,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+        });
+
+        wgpu_render_state
+            .renderer
+            .write()
+            .paint_callback_resources
+            .insert(CubeRenderResources {
+                pipeline,
+                bind_group,
+                uniform_buffer,
+                vertex_buffer,
+                index_buffer,
+            });
+
+        Self { counter: 0.0f32 }
+    }
+}
+
+impl Renderer3d for WgpuRenderer {
+    fn paint(&mut self, ui: &mut egui::Ui, rect: egui::Rect, angle: f32) {
+        let aspect_ratio = rect.width() / rect.height();
+
+        if self.counter > 1.0f32 {
+            self.counter = 0.0f32;
+        }
+        self.counter += 0.05f32;
+
+        let counter = self.counter;
+        let callback = egui::PaintCallback {
+            rect,
+            callback: std::sync::Arc::new(
+                egui_wgpu::CallbackFn::new()
+                    .prepare(move |_device, queue, _encoder, resources| {
+                        let resources: &CubeRenderResources = resources.get().unwrap();
+                        resources.prepare(queue, angle, aspect_ratio, counter);
+                        Vec::new()
+                    })
+                    .paint(move |_info, render_pass, resources| {
+                        let resources: &CubeRenderResources = resources.get().unwrap();
+                        resources.paint(render_pass);
+                    }),
+            ),
+        };
+        ui.painter().add(callback);
+    }
+
+    fn destroy(&mut self, _gl: Option<&glow::Context>) {
+        // The pipeline, buffers and bind group live in `paint_callback_resources` and are
+        // dropped along with eframe's `egui_wgpu::Renderer`; there's nothing to free here.
+    }
+}
+
+struct CubeRenderResources {
+    pipeline: wgpu::RenderPipeline,
+    bind_group: wgpu::BindGroup,
+    uniform_buffer: wgpu::Buffer,
+    vertex_buffer: wgpu::Buffer,
+    index_buffer: wgpu::Buffer,
+}
+
+impl CubeRenderResources {
+    fn prepare(&self, queue: &wgpu::Queue, angle: f32, aspect_ratio: f32, counter: f32) {
+        let uniforms = Uniforms::new(angle, aspect_ratio, counter);
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
+    }
+
+    fn paint<'rp>(&'rp self, render_pass: &mut wgpu::RenderPass<'rp>) {
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        render_pass.draw_indexed(0..INDICES.len() as u32, 0, 0..1);
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    transform: [f32; 16],
+    projection: [f32; 16],
+    color_flow: [f32; 16],
+}
+
+impl Uniforms {
+    fn new(angle: f32, aspect_ratio: f32, counter: f32) -> Self {
+        // Rotate about the Y axis, matching the glow backend's turntable rotation.
+        let (sin_a, cos_a) = angle.sin_cos();
+        #[rustfmt::skip]
+        let transform = [
+            cos_a, 0.0, -sin_a, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            sin_a, 0.0, cos_a, 0.0,
+            0.0, 0.0, -3.0, 1.0,
+        ];
+
+        let fovy: f32 = 45.0f32.to_radians();
+        let (near, far) = (0.1f32, 10.0f32);
+        let f = 1.0 / (fovy / 2.0).tan();
+        #[rustfmt::skip]
+        let projection = [
+            f / aspect_ratio, 0.0, 0.0, 0.0,
+            0.0, f, 0.0, 0.0,
+            0.0, 0.0, (far + near) / (near - far), -1.0,
+            0.0, 0.0, (2.0 * far * near) / (near - far), 0.0,
+        ];
+
+        let (sin_c, cos_c) = (counter * std::f32::consts::TAU).sin_cos();
+        #[rustfmt::skip]
+        let color_flow = [
+            cos_c, sin_c, 0.0, 0.0,
+            -sin_c, cos_c, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+        ];
+
+        Self {
+            transform,
+            projection,
+            color_flow,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Vertex {
+    position: [f32; 3],
+    color: [f32; 3],
+}
+
+impl Vertex {
+    const ATTRIBUTES: [wgpu::VertexAttribute; 2] =
+        wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3];
+
+    fn layout() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &Self::ATTRIBUTES,
+        }
+    }
+}
+
+#[rustfmt::skip]
+const VERTICES: [Vertex; 8] = [
+    Vertex { position: [-0.5, -0.5, -0.5], color: [1.0, 0.0, 0.0] },
+    Vertex { position: [ 0.5, -0.5, -0.5], color: [0.0, 1.0, 0.0] },
+    Vertex { position: [ 0.5,  0.5, -0.5], color: [0.0, 0.0, 1.0] },
+    Vertex { position: [-0.5,  0.5, -0.5], color: [1.0, 1.0, 0.0] },
+    Vertex { position: [-0.5, -0.5,  0.5], color: [1.0, 0.0, 1.0] },
+    Vertex { position: [ 0.5, -0.5,  0.5], color: [0.0, 1.0, 1.0] },
+    Vertex { position: [ 0.5,  0.5,  0.5], color: [1.0, 1.0, 1.0] },
+    Vertex { position: [-0.5,  0.5,  0.5], color: [0.0, 0.0, 0.0] },
+];
+
+#[rustfmt::skip]
+const INDICES: [u32; 36] = [
+    0, 1, 2, 2, 3, 0, // back
+    4, 5, 6, 6, 7, 4, // front
+    0, 3, 7, 7, 4, 0, // left
+    1, 5, 6, 6, 2, 1, // right
+    0, 4, 5, 5, 1, 0, // bottom
+    3, 2, 6, 6, 7, 3, // top
+];
+
+const SHADER_SOURCE: &str = r#"
+struct Uniforms {
+    transform: mat4x4<f32>,
+    projection: mat4x4<f32>,
+    color_flow: mat4x4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> uniforms: Uniforms;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+};
+
+@vertex
+fn vs_main(@location(0) position: vec3<f32>, @location(1) color: vec3<f32>) -> VertexOutput {
+    var out: VertexOutput;
+    out.clip_position = uniforms.projection * uniforms.transform * vec4<f32>(position, 1.0);
+    out.color = uniforms.color_flow * vec4<f32>(color, 1.0);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return in.color;
+}
+"#;